@@ -2,34 +2,68 @@
 #![feature(tuple_trait)]
 #![feature(unboxed_closures)]
 
-use std::{marker::Tuple, rc};
+use std::{
+    cell::{Cell, RefCell},
+    marker::Tuple,
+    rc,
+};
 
 #[derive(Clone)]
 pub struct Handle(rc::Rc<()>);
 
-struct Callback<Args> {
+enum CallbackKind<Args, Out> {
+    /// A regular callback that may fire on every dispatch.
+    Repeating(Box<dyn FnMut<Args, Output = Out>>),
+    /// A one-shot callback consumed on its first dispatch; the slot is emptied
+    /// once fired so the entry reports itself dead and is retained away.
+    Once(Option<Box<dyn FnOnce<Args, Output = Out>>>),
+}
+
+struct Callback<Args, Out = ()> {
     alive: rc::Weak<()>,
-    closure: Box<dyn FnMut<Args, Output = ()>>,
+    kind: CallbackKind<Args, Out>,
 }
 
-impl<Args: Tuple> Callback<Args> {
+impl<Args: Tuple, Out> Callback<Args, Out> {
     fn is_alive(&self) -> bool {
-        self.alive.upgrade().is_some()
+        // A spent one-shot callback is dead regardless of its handle.
+        let consumed = matches!(self.kind, CallbackKind::Once(None));
+        !consumed && self.alive.upgrade().is_some()
     }
 
-    fn call(&mut self, args: Args) {
-        self.closure.call_mut(args);
+    fn call(&mut self, args: Args) -> Out {
+        match &mut self.kind {
+            CallbackKind::Repeating(closure) => closure.call_mut(args),
+            CallbackKind::Once(slot) => {
+                let closure = slot.take().expect("one-shot callback fired twice");
+                closure.call_once(args)
+            }
+        }
     }
 }
 
-fn wrap<F, Args: Tuple>(callback: F) -> (Handle, Callback<Args>)
+fn wrap<F, Args: Tuple, Out>(callback: F) -> (Handle, Callback<Args, Out>)
 where
-    F: FnMut<Args, Output = ()> + 'static,
+    F: FnMut<Args, Output = Out> + 'static,
 {
+    let (handle, alive) = new_handle();
+    let kind = CallbackKind::Repeating(Box::new(callback));
+    (handle, Callback { alive, kind })
+}
+
+fn wrap_once<F, Args: Tuple, Out>(callback: F) -> (Handle, Callback<Args, Out>)
+where
+    F: FnOnce<Args, Output = Out> + 'static,
+{
+    let (handle, alive) = new_handle();
+    let kind = CallbackKind::Once(Some(Box::new(callback)));
+    (handle, Callback { alive, kind })
+}
+
+fn new_handle() -> (Handle, rc::Weak<()>) {
     let handle = rc::Rc::new(());
     let alive = rc::Rc::downgrade(&handle);
-    let closure = Box::new(callback);
-    (Handle(handle), Callback { alive, closure })
+    (Handle(handle), alive)
 }
 
 #[derive(Default)]
@@ -47,6 +81,17 @@ impl<Args: Tuple> CallbackManager<Args> {
         handle
     }
 
+    /// Register a callback that fires exactly once, on the next [`run_all`](Self::run_all),
+    /// and then removes itself — even if its [`Handle`] is still alive.
+    pub fn add_once<F>(&mut self, callback: F) -> Handle
+    where
+        F: FnOnce<Args, Output = ()> + 'static,
+    {
+        let (handle, callback) = wrap_once(callback);
+        self.callbacks.push(callback);
+        handle
+    }
+
     pub fn run_all(&mut self, args: Args)
     where
         Args: Clone,
@@ -58,6 +103,184 @@ impl<Args: Tuple> CallbackManager<Args> {
     }
 }
 
+/// Shared state behind a [`SharedCallbackManager`] handle.
+///
+/// `pending` buffers callbacks registered while a dispatch is in flight and
+/// `queued` buffers emissions triggered from inside a callback; `depth` tells
+/// `add`/`run_all` whether a dispatch is currently running so they route into
+/// those buffers instead of aliasing the list being iterated.
+struct Shared<Args> {
+    callbacks: RefCell<Vec<Callback<Args>>>,
+    pending: RefCell<Vec<Callback<Args>>>,
+    queued: RefCell<Vec<Args>>,
+    depth: Cell<usize>,
+}
+
+impl<Args> Default for Shared<Args> {
+    fn default() -> Self {
+        Self {
+            callbacks: RefCell::new(Vec::new()),
+            pending: RefCell::new(Vec::new()),
+            queued: RefCell::new(Vec::new()),
+            depth: Cell::new(0),
+        }
+    }
+}
+
+/// Cloneable handle to a shared callback list.
+///
+/// Unlike [`CallbackManager`], both `add` and `run_all` take `&self`: the
+/// callbacks live behind an `Rc`, so cloning the manager yields another handle
+/// to the *same* list rather than a separate copy. This lets the same emitter
+/// be registered in several owners without threading `&mut` everywhere.
+/// [`Handle`] still governs liveness exactly as before.
+///
+/// Dispatch is reentrant: a callback may register new callbacks or emit again
+/// on the same manager without tripping an already-borrowed panic. A logical
+/// emission never mutates the vector it is iterating — new callbacks are held
+/// back until the pass finishes and recursive emissions are drained after the
+/// outer dispatch returns.
+pub struct SharedCallbackManager<Args> {
+    shared: rc::Rc<Shared<Args>>,
+}
+
+impl<Args> Clone for SharedCallbackManager<Args> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: rc::Rc::clone(&self.shared),
+        }
+    }
+}
+
+impl<Args> Default for SharedCallbackManager<Args> {
+    fn default() -> Self {
+        Self {
+            shared: rc::Rc::new(Shared::default()),
+        }
+    }
+}
+
+impl<Args: Tuple> SharedCallbackManager<Args> {
+    pub fn add<F>(&self, callback: F) -> Handle
+    where
+        F: FnMut<Args, Output = ()> + 'static,
+    {
+        let (handle, callback) = wrap(callback);
+        // While a dispatch is running the callback list is borrowed for
+        // iteration, so buffer the new entry and splice it in afterwards.
+        if self.shared.depth.get() > 0 {
+            self.shared.pending.borrow_mut().push(callback);
+        } else {
+            self.shared.callbacks.borrow_mut().push(callback);
+        }
+        handle
+    }
+
+    pub fn run_all(&self, args: Args)
+    where
+        Args: Clone,
+    {
+        // A nested emission must not re-enter the borrow held by the outer
+        // pass; queue it and let the outer loop pick it up.
+        if self.shared.depth.get() > 0 {
+            self.shared.queued.borrow_mut().push(args);
+            return;
+        }
+
+        let mut outstanding = vec![args];
+        let mut next = 0;
+        while next < outstanding.len() {
+            let args = outstanding[next].clone();
+            next += 1;
+            self.dispatch_once(args);
+            outstanding.append(&mut self.shared.queued.borrow_mut());
+        }
+    }
+
+    /// Run one pass over the currently-live callbacks, then splice in any
+    /// callbacks that were registered during the pass and drop dead entries.
+    fn dispatch_once(&self, args: Args)
+    where
+        Args: Clone,
+    {
+        self.shared.depth.set(self.shared.depth.get() + 1);
+        {
+            let mut callbacks = self.shared.callbacks.borrow_mut();
+            // Fix the bound up front so callbacks added during the pass are not
+            // run until the next one.
+            let bound = callbacks.len();
+            for callback in callbacks.iter_mut().take(bound) {
+                if callback.is_alive() {
+                    callback.call(args.clone());
+                }
+            }
+        }
+        self.shared.depth.set(self.shared.depth.get() - 1);
+
+        self.shared
+            .callbacks
+            .borrow_mut()
+            .append(&mut self.shared.pending.borrow_mut());
+        self.shared.callbacks.borrow_mut().retain(Callback::is_alive);
+    }
+}
+
+/// A callback manager whose callbacks return a value of type `R` instead of
+/// being purely side-effecting.
+///
+/// Mirrors [`CallbackManager`] but, rather than discarding results, exposes
+/// [`run_all_collect`](Self::run_all_collect) to gather them and
+/// [`run_all_fold`](Self::run_all_fold) to reduce over them. This supports
+/// patterns like polling a set of validators that each return a `bool` or
+/// `Result` and reducing over the outcomes.
+pub struct ValueCallbackManager<Args, R> {
+    callbacks: Vec<Callback<Args, R>>,
+}
+
+impl<Args, R> Default for ValueCallbackManager<Args, R> {
+    fn default() -> Self {
+        Self {
+            callbacks: Vec::new(),
+        }
+    }
+}
+
+impl<Args: Tuple, R> ValueCallbackManager<Args, R> {
+    pub fn add<F>(&mut self, callback: F) -> Handle
+    where
+        F: FnMut<Args, Output = R> + 'static,
+    {
+        let (handle, callback) = wrap(callback);
+        self.callbacks.push(callback);
+        handle
+    }
+
+    /// Run every live callback and collect their results in registration order.
+    pub fn run_all_collect(&mut self, args: Args) -> Vec<R>
+    where
+        Args: Clone,
+    {
+        self.callbacks.retain(Callback::is_alive);
+        self.callbacks
+            .iter_mut()
+            .map(|f| f.call(args.clone()))
+            .collect()
+    }
+
+    /// Run every live callback in registration order, threading `init` through
+    /// `combine` with each result to produce a single accumulated value.
+    pub fn run_all_fold<B, C>(&mut self, args: Args, init: B, mut combine: C) -> B
+    where
+        Args: Clone,
+        C: FnMut(B, R) -> B,
+    {
+        self.callbacks.retain(Callback::is_alive);
+        self.callbacks
+            .iter_mut()
+            .fold(init, |acc, f| combine(acc, f.call(args.clone())))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::cell::RefCell;
@@ -110,4 +333,141 @@ mod tests {
         manager.run_all((42,));
         assert_eq!(counts.borrow().as_slice(), &[42, 42, 0]);
     }
+
+    #[test]
+    fn test_shared_clone_shares_callbacks() {
+        let manager = SharedCallbackManager::default();
+
+        // slice to check side-effects
+        let counts = rc::Rc::new(RefCell::new([0; 3]));
+
+        // register through a cloned handle to the same list
+        let other = manager.clone();
+        let _handles: Vec<Handle> = counts
+            .borrow()
+            .iter()
+            .enumerate()
+            .map(|(idx, _)| {
+                let counts = rc::Rc::clone(&counts);
+                other.add(move |n: usize| RefCell::borrow_mut(&counts)[idx] += n)
+            })
+            .collect();
+
+        // emitting through the original handle runs the callbacks added via the clone
+        manager.run_all((42,));
+        assert_eq!(counts.borrow().as_slice(), &[42, 42, 42]);
+    }
+
+    #[test]
+    fn test_reentrant_add_runs_next_pass() {
+        let manager: SharedCallbackManager<()> = SharedCallbackManager::default();
+
+        let calls = rc::Rc::new(RefCell::new(Vec::new()));
+        let added = rc::Rc::new(RefCell::new(false));
+        let inner_handle: rc::Rc<RefCell<Option<Handle>>> = rc::Rc::new(RefCell::new(None));
+
+        // This callback registers a second callback the first time it fires; the
+        // new callback must not run until the following pass.
+        let _handle = {
+            let registrar = manager.clone();
+            let manager = manager.clone();
+            let calls = rc::Rc::clone(&calls);
+            let added = rc::Rc::clone(&added);
+            // keep the reentrantly-added callback alive past the closure call
+            let inner_handle = rc::Rc::clone(&inner_handle);
+            registrar.add(move || {
+                RefCell::borrow_mut(&calls).push("outer");
+                if !*added.borrow() {
+                    *added.borrow_mut() = true;
+                    let calls = rc::Rc::clone(&calls);
+                    *inner_handle.borrow_mut() =
+                        Some(manager.add(move || RefCell::borrow_mut(&calls).push("inner")));
+                }
+            })
+        };
+
+        manager.run_all(());
+        // first pass: only the original callback ran
+        assert_eq!(calls.borrow().as_slice(), &["outer"]);
+
+        manager.run_all(());
+        // second pass: both the original and the deferred callback ran
+        assert_eq!(calls.borrow().as_slice(), &["outer", "outer", "inner"]);
+    }
+
+    #[test]
+    fn test_reentrant_run_all_is_queued() {
+        let manager: SharedCallbackManager<(u32,)> = SharedCallbackManager::default();
+
+        let seen = rc::Rc::new(RefCell::new(Vec::new()));
+        let fired = rc::Rc::new(RefCell::new(false));
+
+        let _handle = {
+            let registrar = manager.clone();
+            let manager = manager.clone();
+            let seen = rc::Rc::clone(&seen);
+            let fired = rc::Rc::clone(&fired);
+            registrar.add(move |n: u32| {
+                RefCell::borrow_mut(&seen).push(n);
+                // Re-emit once from inside dispatch; it must be drained after
+                // the current pass rather than aliasing the live iteration.
+                if !*fired.borrow() {
+                    *fired.borrow_mut() = true;
+                    manager.run_all((99,));
+                }
+            })
+        };
+
+        manager.run_all((1,));
+        assert_eq!(seen.borrow().as_slice(), &[1, 99]);
+    }
+
+    #[test]
+    fn test_run_all_collect() {
+        let mut manager: ValueCallbackManager<(i32,), i32> = ValueCallbackManager::default();
+
+        let _handles: Vec<Handle> = (1..=3)
+            .map(|factor| manager.add(move |n: i32| n * factor))
+            .collect();
+
+        assert_eq!(manager.run_all_collect((10,)), vec![10, 20, 30]);
+    }
+
+    #[test]
+    fn test_run_all_fold() {
+        let mut manager: ValueCallbackManager<(), bool> = ValueCallbackManager::default();
+
+        let valid = rc::Rc::new(RefCell::new([true, false, true]));
+
+        let _handles: Vec<Handle> = (0..3)
+            .map(|idx| {
+                let valid = rc::Rc::clone(&valid);
+                manager.add(move || valid.borrow()[idx])
+            })
+            .collect();
+
+        // reduce the validator outcomes into a single "all passed" verdict
+        let all_ok = manager.run_all_fold((), true, |acc, ok| acc && ok);
+        assert!(!all_ok);
+    }
+
+    #[test]
+    fn test_add_once_fires_once() {
+        let mut manager = CallbackManager::default();
+
+        let count = rc::Rc::new(RefCell::new(0));
+
+        // one-shot callback; its handle is kept alive for the whole test
+        let _handle = {
+            let count = rc::Rc::clone(&count);
+            manager.add_once(move |n: usize| *RefCell::borrow_mut(&count) += n)
+        };
+
+        manager.run_all((1,));
+        manager.run_all((1,));
+        manager.run_all((1,));
+
+        // fired on the first dispatch only, despite its handle still being alive
+        assert_eq!(*count.borrow(), 1);
+    }
 }